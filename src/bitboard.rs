@@ -0,0 +1,226 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::game::{Color, MailBoxBoard, Piece};
+
+/// A set of squares packed into a single `u64`, one bit per square, indexed
+/// as `row * 8 + col` to match the row-major layout of [`MailBoxBoard`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+
+    pub fn set(&mut self, square: usize) {
+        self.0 |= 1 << square;
+    }
+
+    pub fn clear(&mut self, square: usize) {
+        self.0 &= !(1 << square);
+    }
+
+    pub fn is_set(&self, square: usize) -> bool {
+        self.0 & (1 << square) != 0
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+    fn bitor(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+    fn bitand(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 & rhs.0)
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+    fn bitxor(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+    fn not(self) -> Bitboard {
+        Bitboard(!self.0)
+    }
+}
+
+const fn rank_mask(row: usize) -> u64 {
+    0xFFu64 << (row * 8)
+}
+
+const fn file_mask(col: usize) -> u64 {
+    let mut mask = 0u64;
+    let mut row = 0;
+    while row < 8 {
+        mask |= 1u64 << (row * 8 + col);
+        row += 1;
+    }
+    mask
+}
+
+pub const RANKS: [Bitboard; 8] = [
+    Bitboard(rank_mask(0)),
+    Bitboard(rank_mask(1)),
+    Bitboard(rank_mask(2)),
+    Bitboard(rank_mask(3)),
+    Bitboard(rank_mask(4)),
+    Bitboard(rank_mask(5)),
+    Bitboard(rank_mask(6)),
+    Bitboard(rank_mask(7)),
+];
+
+pub const FILES: [Bitboard; 8] = [
+    Bitboard(file_mask(0)),
+    Bitboard(file_mask(1)),
+    Bitboard(file_mask(2)),
+    Bitboard(file_mask(3)),
+    Bitboard(file_mask(4)),
+    Bitboard(file_mask(5)),
+    Bitboard(file_mask(6)),
+    Bitboard(file_mask(7)),
+];
+
+const PIECE_KINDS: usize = 6;
+const COLORS: usize = 2;
+
+pub(crate) fn piece_kind_index(piece: Piece) -> Option<usize> {
+    match piece {
+        Piece::Pawn(_) => Some(0),
+        Piece::Knight(_) => Some(1),
+        Piece::Bishop(_) => Some(2),
+        Piece::Rook(_) => Some(3),
+        Piece::Queen(_) => Some(4),
+        Piece::King(_) => Some(5),
+        Piece::Empty => None,
+    }
+}
+
+pub(crate) fn piece_color(piece: Piece) -> Option<Color> {
+    match piece {
+        Piece::Pawn(color)
+        | Piece::Knight(color)
+        | Piece::Bishop(color)
+        | Piece::Rook(color)
+        | Piece::Queen(color)
+        | Piece::King(color) => Some(color),
+        Piece::Empty => None,
+    }
+}
+
+pub(crate) fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// A bitboard-backed mirror of a [`MailBoxBoard`]: one occupancy bitboard
+/// per piece type, one per color, and their union, so attack/occupancy
+/// queries are a handful of bitwise operations instead of a board scan.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BitboardBoard {
+    pieces: [Bitboard; PIECE_KINDS],
+    colors: [Bitboard; COLORS],
+    combined_occupancy: Bitboard,
+}
+
+impl BitboardBoard {
+    pub fn from_mailbox(board: &MailBoxBoard) -> BitboardBoard {
+        let mut pieces = [Bitboard::EMPTY; PIECE_KINDS];
+        let mut colors = [Bitboard::EMPTY; COLORS];
+        let mut combined_occupancy = Bitboard::EMPTY;
+
+        for (row_index, row) in board.iter().enumerate() {
+            for (col_index, piece) in row.iter().enumerate() {
+                let Some(kind) = piece_kind_index(*piece) else {
+                    continue;
+                };
+                let color = piece_color(*piece).expect("non-empty piece has a color");
+                let square = row_index * 8 + col_index;
+
+                pieces[kind].set(square);
+                colors[color_index(color)].set(square);
+                combined_occupancy.set(square);
+            }
+        }
+
+        BitboardBoard {
+            pieces,
+            colors,
+            combined_occupancy,
+        }
+    }
+
+    pub fn pieces(&self, piece: Piece) -> Bitboard {
+        match (piece_kind_index(piece), piece_color(piece)) {
+            (Some(kind), Some(color)) => self.pieces[kind] & self.colors[color_index(color)],
+            _ => Bitboard::EMPTY,
+        }
+    }
+
+    pub fn color(&self, color: Color) -> Bitboard {
+        self.colors[color_index(color)]
+    }
+
+    pub fn occupancy(&self) -> Bitboard {
+        self.combined_occupancy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_boards::STARTING_BOARD;
+
+    #[test]
+    fn clear_unsets_a_square_without_touching_the_rest() {
+        let mut board = Bitboard::EMPTY;
+        board.set(10);
+        board.set(20);
+
+        board.clear(10);
+
+        assert!(!board.is_set(10));
+        assert!(board.is_set(20));
+    }
+
+    #[test]
+    fn ranks_and_files_have_eight_squares_each() {
+        for rank in RANKS {
+            assert_eq!(rank.count(), 8);
+        }
+        for file in FILES {
+            assert_eq!(file.count(), 8);
+        }
+    }
+
+    #[test]
+    fn starting_position_occupancy_has_thirty_two_pieces() {
+        let bitboards = BitboardBoard::from_mailbox(&STARTING_BOARD);
+
+        assert_eq!(bitboards.occupancy().count(), 32);
+        assert_eq!(bitboards.color(Color::White).count(), 16);
+        assert_eq!(bitboards.color(Color::Black).count(), 16);
+        assert_eq!(bitboards.pieces(Piece::Pawn(Color::White)).count(), 8);
+        assert_eq!(bitboards.pieces(Piece::King(Color::White)).count(), 1);
+    }
+
+    #[test]
+    fn white_pawns_sit_on_rank_with_row_index_six() {
+        let bitboards = BitboardBoard::from_mailbox(&STARTING_BOARD);
+
+        let white_pawns = bitboards.pieces(Piece::Pawn(Color::White));
+        assert_eq!(white_pawns, white_pawns & RANKS[6]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Fluent, validated construction of a [`ChessGame`] without going through
+//! FEN text.
+
+use crate::error::ErrorWrapper;
+use crate::game::{convert_chess_coordinates, Castling, ChessGame, Color, MailBoxBoard, Piece};
+
+/// Builds a [`ChessGame`] one piece (or one whole board) at a time. Starts
+/// from an empty board with White to move, no castling rights, no
+/// en-passant target, and the standard starting move counters.
+/// [`ChessGameBuilder::build`] runs the same checks
+/// [`ChessGame::validate`] applies to a parsed FEN, so the positions that
+/// check rejects are rejected regardless of how the position was assembled.
+/// The en-passant square is not among those checks: only the FEN parser
+/// checks it, and `build` takes it as given.
+#[derive(Debug)]
+pub struct ChessGameBuilder {
+    board: MailBoxBoard,
+    turn: Color,
+    white_castling: Castling,
+    black_castling: Castling,
+    en_passant: Option<(usize, usize)>,
+    halfmove: usize,
+    fullmove: usize,
+}
+
+impl Default for ChessGameBuilder {
+    fn default() -> ChessGameBuilder {
+        ChessGameBuilder {
+            board: [[Piece::Empty; 8]; 8],
+            turn: Color::White,
+            white_castling: Castling::None,
+            black_castling: Castling::None,
+            en_passant: None,
+            halfmove: 0,
+            fullmove: 1,
+        }
+    }
+}
+
+impl ChessGameBuilder {
+    pub fn new() -> ChessGameBuilder {
+        ChessGameBuilder::default()
+    }
+
+    /// Replaces the whole board in one go.
+    pub fn board(mut self, board: MailBoxBoard) -> ChessGameBuilder {
+        self.board = board;
+        self
+    }
+
+    /// Places a single piece at `(row, col)`, matching the row-major layout
+    /// of [`MailBoxBoard`] (row 0 is rank 8, col 0 is the a-file).
+    pub fn piece(mut self, row: usize, col: usize, piece: Piece) -> ChessGameBuilder {
+        self.board[row][col] = piece;
+        self
+    }
+
+    /// Places a single piece at an algebraic square such as "e4".
+    pub fn square(mut self, square: &str, piece: Piece) -> Result<ChessGameBuilder, ErrorWrapper> {
+        let (file_index, rank_index) = convert_chess_coordinates(square)?;
+        self.board[rank_index][file_index] = piece;
+        Ok(self)
+    }
+
+    pub fn turn(mut self, turn: Color) -> ChessGameBuilder {
+        self.turn = turn;
+        self
+    }
+
+    pub fn castling(mut self, color: Color, rights: Castling) -> ChessGameBuilder {
+        match color {
+            Color::White => self.white_castling = rights,
+            Color::Black => self.black_castling = rights,
+        }
+        self
+    }
+
+    pub fn en_passant(mut self, en_passant: Option<(usize, usize)>) -> ChessGameBuilder {
+        self.en_passant = en_passant;
+        self
+    }
+
+    pub fn halfmove(mut self, halfmove: usize) -> ChessGameBuilder {
+        self.halfmove = halfmove;
+        self
+    }
+
+    pub fn fullmove(mut self, fullmove: usize) -> ChessGameBuilder {
+        self.fullmove = fullmove;
+        self
+    }
+
+    /// Assembles and validates the position.
+    pub fn build(self) -> Result<ChessGame, ErrorWrapper> {
+        let game = ChessGame::new(
+            self.board,
+            self.turn,
+            self.white_castling,
+            self.black_castling,
+            self.en_passant,
+            self.halfmove,
+            self.fullmove,
+        );
+        game.validate()?;
+        Ok(game)
+    }
+}
+
+impl TryFrom<ChessGameBuilder> for ChessGame {
+    type Error = ErrorWrapper;
+
+    fn try_from(builder: ChessGameBuilder) -> Result<ChessGame, ErrorWrapper> {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_boards::STARTING_BOARD;
+
+    #[test]
+    fn build_runs_validation() {
+        let result = ChessGameBuilder::new()
+            .board(STARTING_BOARD)
+            .castling(Color::White, Castling::None)
+            .castling(Color::Black, Castling::None)
+            .build();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn build_rejects_a_missing_king() {
+        let mut board = STARTING_BOARD;
+        board[7][4] = Piece::Empty;
+
+        let result = ChessGameBuilder::new().board(board).build();
+
+        assert_eq!(result, Err(ErrorWrapper::MissingKing(Color::White)));
+    }
+
+    #[test]
+    fn placing_pieces_one_at_a_time_matches_placing_a_whole_board() {
+        let from_board = ChessGameBuilder::new()
+            .board(STARTING_BOARD)
+            .castling(
+                Color::White,
+                Castling::Both {
+                    kingside_rook_file: 7,
+                    queenside_rook_file: 0,
+                },
+            )
+            .build()
+            .unwrap();
+
+        let mut builder = ChessGameBuilder::new().castling(
+            Color::White,
+            Castling::Both {
+                kingside_rook_file: 7,
+                queenside_rook_file: 0,
+            },
+        );
+        for (row_index, row) in STARTING_BOARD.iter().enumerate() {
+            for (col_index, piece) in row.iter().enumerate() {
+                builder = builder.piece(row_index, col_index, *piece);
+            }
+        }
+        let from_pieces = builder.build().unwrap();
+
+        assert_eq!(from_board.hash(), from_pieces.hash());
+    }
+
+    #[test]
+    fn square_places_a_piece_at_an_algebraic_square() {
+        let game = ChessGameBuilder::new()
+            .square("e1", Piece::King(Color::White))
+            .unwrap()
+            .square("e8", Piece::King(Color::Black))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(game.board()[7][4], Piece::King(Color::White));
+        assert_eq!(game.board()[0][4], Piece::King(Color::Black));
+    }
+
+    #[test]
+    fn square_rejects_an_invalid_square() {
+        let result = ChessGameBuilder::new().square("z9", Piece::Empty);
+
+        assert_eq!(result.unwrap_err(), ErrorWrapper::InvalidCoordinates);
+    }
+}
@@ -1,11 +1,22 @@
 use core::fmt;
 use std::{error::Error, num::ParseIntError};
 
+use crate::game::Color;
+
 #[derive(Debug, PartialEq)]
 pub enum ErrorWrapper {
     InvalidFen,
     InvalidCoordinates,
     InvalidNumber,
+    InvalidEnPassant,
+    MissingKing(Color),
+    MultipleKings(Color),
+    PawnOnBackRank,
+    KingsAdjacent,
+    TooManyPawns(Color),
+    TooManyPieces(Color),
+    InvalidCastlingRights,
+    InvalidMove,
 }
 
 impl fmt::Display for ErrorWrapper {
@@ -14,6 +25,17 @@ impl fmt::Display for ErrorWrapper {
             ErrorWrapper::InvalidFen => write!(f, "Invalid FEN"),
             ErrorWrapper::InvalidCoordinates => write!(f, "Invalid Coordinates"),
             ErrorWrapper::InvalidNumber => write!(f, "Invalid Number"),
+            ErrorWrapper::InvalidEnPassant => write!(f, "Invalid en passant square"),
+            ErrorWrapper::MissingKing(color) => write!(f, "{:?} has no king", color),
+            ErrorWrapper::MultipleKings(color) => write!(f, "{:?} has more than one king", color),
+            ErrorWrapper::PawnOnBackRank => write!(f, "A pawn is on the first or last rank"),
+            ErrorWrapper::KingsAdjacent => write!(f, "The two kings are on adjacent squares"),
+            ErrorWrapper::TooManyPawns(color) => write!(f, "{:?} has more than 8 pawns", color),
+            ErrorWrapper::TooManyPieces(color) => write!(f, "{:?} has more than 16 pieces", color),
+            ErrorWrapper::InvalidCastlingRights => {
+                write!(f, "Castling rights are inconsistent with the board")
+            }
+            ErrorWrapper::InvalidMove => write!(f, "The move is not well-formed"),
         }
     }
 }
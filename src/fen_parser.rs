@@ -1,6 +1,10 @@
 use crate::{
+    builder::ChessGameBuilder,
     error::ErrorWrapper,
-    game::{convert_chess_coordinates, Castling, ChessGame, Color, MailBoxBoard, Piece},
+    game::{
+        convert_chess_coordinates, find_king, home_rank, Castling, ChessGame, Color,
+        MailBoxBoard, Piece,
+    },
 };
 
 pub const BLACK: Color = Color::Black;
@@ -25,13 +29,28 @@ fn fen_piece_to_piece(fen_piece: char) -> Result<Piece, ErrorWrapper> {
     })
 }
 
+const STARTPOS_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Splits a FEN into its six fields. Only the piece placement field is
+/// mandatory; any trailing fields that are missing fall back to the
+/// standard defaults (`w`, `-`, `-`, `0`, `1`), multiple spaces between
+/// fields are tolerated, and the literal `"startpos"` is recognized as the
+/// standard starting position.
 pub fn parse_fen(fen: &str) -> Result<(&str, &str, &str, &str, &str, &str), ErrorWrapper> {
-    let [piece_placement, active_color, castling, en_passant, halfmoves, fullmoves] =
-        fen.split(" ").collect::<Vec<&str>>()[0..6]
-    else {
-        return Err(ErrorWrapper::InvalidFen);
+    let fen = if fen.trim() == "startpos" {
+        STARTPOS_FEN
+    } else {
+        fen
     };
 
+    let mut fields = fen.split_whitespace();
+    let piece_placement = fields.next().ok_or(ErrorWrapper::InvalidFen)?;
+    let active_color = fields.next().unwrap_or("w");
+    let castling = fields.next().unwrap_or("-");
+    let en_passant = fields.next().unwrap_or("-");
+    let halfmoves = fields.next().unwrap_or("0");
+    let fullmoves = fields.next().unwrap_or("1");
+
     Ok((
         piece_placement,
         active_color,
@@ -50,22 +69,22 @@ pub fn create_from_fen(fen: &str) -> Result<ChessGame, ErrorWrapper> {
 
     let board = parse_piece_placement(piece_placement)?;
 
-    let (white_castling, black_castling) = parse_castling_rights(castling)?;
+    let (white_castling, black_castling) = parse_castling_rights(castling, &board)?;
 
-    let en_passant = parse_en_passant_square(en_passant)?;
+    let en_passant = parse_en_passant_square(en_passant, &board, color)?;
 
     let halfmoves = parse_moves(halfmoves)?;
     let fullmoves = parse_moves(fullmoves)?;
 
-    Ok(ChessGame::new(
-        board,
-        color,
-        white_castling,
-        black_castling,
-        en_passant,
-        halfmoves,
-        fullmoves,
-    ))
+    ChessGameBuilder::new()
+        .board(board)
+        .turn(color)
+        .castling(Color::White, white_castling)
+        .castling(Color::Black, black_castling)
+        .en_passant(en_passant)
+        .halfmove(halfmoves)
+        .fullmove(fullmoves)
+        .build()
 }
 
 fn parse_piece_placement(piece_placement: &str) -> Result<MailBoxBoard, ErrorWrapper> {
@@ -103,42 +122,104 @@ pub fn parse_active_color(active_color: &str) -> Result<Color, ErrorWrapper> {
     })
 }
 
-pub fn parse_castling_rights(castling: &str) -> Result<(Castling, Castling), ErrorWrapper> {
-    let mut white_castling = Castling::None;
-    let mut black_castling = Castling::None;
+enum CastlingSide {
+    KingSide,
+    QueenSide,
+}
+
+/// The file of `color`'s king on its home rank, if one is there.
+fn king_file(board: &MailBoxBoard, color: Color) -> Option<usize> {
+    find_king(board, color)
+        .into_iter()
+        .find(|&(row, _)| row == home_rank(color))
+        .map(|(_, col)| col)
+}
+
+/// Parses the castling field, accepting both the standard `KQkq` spelling
+/// and X-FEN / Shredder-FEN file-letter castling rights (`a`-`h` / `A`-`H`),
+/// where the letter names the rook's starting file directly. `K`/`Q` are
+/// treated as the h-file/a-file rooks respectively, matching the files a
+/// file letter would have to name to mean the same thing on a standard
+/// board. A bare file letter is classified kingside when it names a file
+/// east of that side's own king and queenside otherwise, since the board's
+/// center is meaningless once the king isn't on the e-file (Chess960).
+pub fn parse_castling_rights(
+    castling: &str,
+    board: &MailBoxBoard,
+) -> Result<(Castling, Castling), ErrorWrapper> {
+    let mut white_kingside = None;
+    let mut white_queenside = None;
+    let mut black_kingside = None;
+    let mut black_queenside = None;
+
     for char in castling.chars() {
         if char == '-' {
-            return Ok((white_castling, black_castling));
+            return Ok((Castling::None, Castling::None));
         }
 
-        let castling = match char.to_ascii_lowercase() {
-            'k' => Castling::KingSide,
-            'q' => Castling::QueenSide,
+        let is_white = char.is_uppercase();
+        let color = if is_white { Color::White } else { Color::Black };
+
+        let (side, rook_file) = match char.to_ascii_uppercase() {
+            'K' => (CastlingSide::KingSide, 7),
+            'Q' => (CastlingSide::QueenSide, 0),
+            'A'..='H' => {
+                let file = char.to_ascii_uppercase() as usize - 'A' as usize;
+                let king_file = king_file(board, color).ok_or(ErrorWrapper::InvalidCastlingRights)?;
+                let side = if file > king_file {
+                    CastlingSide::KingSide
+                } else {
+                    CastlingSide::QueenSide
+                };
+                (side, file)
+            }
             _ => return Err(ErrorWrapper::InvalidFen),
         };
 
-        let target_castling = if char.is_lowercase() {
-            &mut black_castling
+        let (kingside, queenside) = if is_white {
+            (&mut white_kingside, &mut white_queenside)
         } else {
-            &mut white_castling
+            (&mut black_kingside, &mut black_queenside)
         };
 
-        *target_castling = match (*target_castling, castling) {
-            (Castling::KingSide, Castling::QueenSide) => Castling::Both,
-            (_, new_castling) => new_castling,
-        };
+        match side {
+            CastlingSide::KingSide => *kingside = Some(rook_file),
+            CastlingSide::QueenSide => *queenside = Some(rook_file),
+        }
     }
 
-    Ok((white_castling, black_castling))
+    Ok((
+        Castling::from_rook_files(white_kingside, white_queenside),
+        Castling::from_rook_files(black_kingside, black_queenside),
+    ))
 }
 
-fn parse_en_passant_square(en_passant: &str) -> Result<Option<(usize, usize)>, ErrorWrapper> {
-    match en_passant {
-        "-" => Ok(None),
-        en_passant_square => {
-            convert_chess_coordinates(en_passant_square).map(|square| Some(square))
-        }
+/// Parses the en-passant field, rejecting any square that couldn't actually
+/// have been produced by a legal double pawn push: the target square must
+/// sit on rank 3 (white just pushed, black to move) or rank 6 (black just
+/// pushed, white to move), be empty, and have the pushing side's pawn on
+/// the square directly behind it.
+fn parse_en_passant_square(
+    en_passant: &str,
+    board: &MailBoxBoard,
+    active_color: Color,
+) -> Result<Option<(usize, usize)>, ErrorWrapper> {
+    let (file, rank) = match en_passant {
+        "-" => return Ok(None),
+        en_passant_square => convert_chess_coordinates(en_passant_square)?,
+    };
+
+    let (pusher, pawn_rank) = match (rank, active_color) {
+        (5, Color::Black) => (Color::White, rank - 1),
+        (2, Color::White) => (Color::Black, rank + 1),
+        _ => return Err(ErrorWrapper::InvalidEnPassant),
+    };
+
+    if board[rank][file] != Piece::Empty || board[pawn_rank][file] != Piece::Pawn(pusher) {
+        return Err(ErrorWrapper::InvalidEnPassant);
     }
+
+    Ok(Some((file, rank)))
 }
 
 fn parse_moves(moves: &str) -> Result<usize, ErrorWrapper> {
@@ -152,7 +233,7 @@ pub mod tests {
     use crate::{
         error::ErrorWrapper,
         fen_parser::{parse_en_passant_square, parse_moves, parse_piece_placement, BLACK, WHITE},
-        game::{Castling, ChessGame},
+        game::{Castling, ChessGame, Piece},
         test_boards::{BOARD2, BOARD3, STARTING_BOARD},
     };
 
@@ -211,65 +292,110 @@ pub mod tests {
         assert_eq!(result, Err(ErrorWrapper::InvalidFen));
     }
 
+    const STANDARD_BOTH: Castling = Castling::Both {
+        kingside_rook_file: 7,
+        queenside_rook_file: 0,
+    };
+
     #[test]
     fn complete_castling_rights() {
         let castling = "KQkq";
 
-        let result = parse_castling_rights(castling).unwrap();
+        let result = parse_castling_rights(castling, &STARTING_BOARD).unwrap();
 
-        assert_eq!(result, (Castling::Both, Castling::Both));
+        assert_eq!(result, (STANDARD_BOTH, STANDARD_BOTH));
     }
 
     #[test]
     fn white_kingside_castling_rights() {
         let castling = "Kkq";
 
-        let result = parse_castling_rights(castling).unwrap();
+        let result = parse_castling_rights(castling, &STARTING_BOARD).unwrap();
 
-        assert_eq!(result, (Castling::KingSide, Castling::Both));
+        assert_eq!(result, (Castling::KingSide(7), STANDARD_BOTH));
     }
 
     #[test]
     fn white_queenside_castling_rights() {
         let castling = "Qkq";
 
-        let result = parse_castling_rights(castling).unwrap();
+        let result = parse_castling_rights(castling, &STARTING_BOARD).unwrap();
 
-        assert_eq!(result, (Castling::QueenSide, Castling::Both));
+        assert_eq!(result, (Castling::QueenSide(0), STANDARD_BOTH));
     }
 
     #[test]
     fn black_kingside_castling_rights() {
         let castling = "k";
 
-        let result = parse_castling_rights(castling).unwrap();
+        let result = parse_castling_rights(castling, &STARTING_BOARD).unwrap();
 
-        assert_eq!(result, (Castling::None, Castling::KingSide));
+        assert_eq!(result, (Castling::None, Castling::KingSide(7)));
     }
 
     #[test]
     fn black_queenside_castling_rights() {
         let castling = "KQq";
 
-        let result = parse_castling_rights(castling).unwrap();
+        let result = parse_castling_rights(castling, &STARTING_BOARD).unwrap();
 
-        assert_eq!(result, (Castling::Both, Castling::QueenSide));
+        assert_eq!(result, (STANDARD_BOTH, Castling::QueenSide(0)));
     }
 
     #[test]
     fn no_castling_rights() {
         let castling = "-";
 
-        let result = parse_castling_rights(castling).unwrap();
+        let result = parse_castling_rights(castling, &STARTING_BOARD).unwrap();
 
         assert_eq!(result, (Castling::None, Castling::None));
     }
 
+    #[test]
+    fn shredder_fen_castling_rights() {
+        let castling = "HAha";
+
+        let result = parse_castling_rights(castling, &STARTING_BOARD).unwrap();
+
+        assert_eq!(result, (STANDARD_BOTH, STANDARD_BOTH));
+    }
+
+    #[test]
+    fn bare_file_letters_are_classified_relative_to_the_kings_file_not_the_boards_center() {
+        // King on b1, rooks on a1 (queenside) and c1 (kingside): both rook
+        // files sit on the same side of the board's center, so a center-cutoff
+        // heuristic would misclassify one of them.
+        let mut board = [[Piece::Empty; 8]; 8];
+        board[7][1] = Piece::King(WHITE);
+        board[7][0] = Piece::Rook(WHITE);
+        board[7][2] = Piece::Rook(WHITE);
+        board[0][4] = Piece::King(crate::game::Color::Black);
+
+        let result = parse_castling_rights("CA", &board).unwrap();
+
+        assert_eq!(
+            result,
+            (
+                Castling::from_rook_files(Some(2), Some(0)),
+                Castling::None
+            )
+        );
+    }
+
+    fn board_with_en_passant_pawn() -> crate::game::MailBoxBoard {
+        let mut board = [[Piece::Empty; 8]; 8];
+        board[4][4] = Piece::Pawn(WHITE);
+        board
+    }
+
     #[test]
     fn en_passant_square() {
         let square = "e3";
+        let board = board_with_en_passant_pawn();
 
-        let result = parse_en_passant_square(square).unwrap().unwrap();
+        let result = parse_en_passant_square(square, &board, BLACK)
+            .unwrap()
+            .unwrap();
 
         assert_eq!(result, (4, 5));
     }
@@ -277,17 +403,29 @@ pub mod tests {
     #[test]
     fn invalid_en_passant_square() {
         let square = "o1";
+        let board = board_with_en_passant_pawn();
 
-        let result = parse_en_passant_square(square);
+        let result = parse_en_passant_square(square, &board, BLACK);
 
         assert_eq!(result, Err(ErrorWrapper::InvalidCoordinates));
     }
 
+    #[test]
+    fn en_passant_square_without_a_pushed_pawn_is_invalid() {
+        let square = "e3";
+        let board = [[Piece::Empty; 8]; 8];
+
+        let result = parse_en_passant_square(square, &board, BLACK);
+
+        assert_eq!(result, Err(ErrorWrapper::InvalidEnPassant));
+    }
+
     #[test]
     fn no_en_passant() {
         let square = "-";
+        let board = [[Piece::Empty; 8]; 8];
 
-        let result = parse_en_passant_square(square).unwrap();
+        let result = parse_en_passant_square(square, &board, WHITE).unwrap();
 
         assert_eq!(result, None);
     }
@@ -318,15 +456,7 @@ pub mod tests {
 
         assert_eq!(
             result,
-            ChessGame::new(
-                STARTING_BOARD,
-                WHITE,
-                Castling::Both,
-                Castling::Both,
-                None,
-                0,
-                1
-            )
+            ChessGame::new(STARTING_BOARD, WHITE, STANDARD_BOTH, STANDARD_BOTH, None, 0, 1)
         )
     }
 
@@ -341,4 +471,102 @@ pub mod tests {
             ChessGame::new(BOARD2, BLACK, Castling::None, Castling::None, None, 99, 50)
         )
     }
+
+    #[test]
+    fn to_fen_round_trips_starting_position() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+        let game = create_from_fen(fen).unwrap();
+
+        assert_eq!(game.to_fen(), fen);
+    }
+
+    #[test]
+    fn shredder_fen_castling_is_accepted_on_the_standard_back_rank() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1";
+
+        let game = create_from_fen(fen).unwrap();
+
+        assert_eq!(game.castling(WHITE), STANDARD_BOTH);
+        assert_eq!(game.castling(BLACK), STANDARD_BOTH);
+    }
+
+    #[test]
+    fn to_fen_round_trips_en_passant_position() {
+        let fen = "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3";
+
+        let game = create_from_fen(fen).unwrap();
+
+        assert_eq!(game.to_fen(), fen);
+    }
+
+    #[test]
+    fn to_fen_round_trips_non_standard_castling_rights_off_the_e_file() {
+        // Same board as `bare_file_letters_are_classified_relative_to_the_kings_file_not_the_boards_center`:
+        // White king on b1 with rooks on a1 (queenside) and c1 (kingside), so
+        // the kingside right needs a non-standard file letter ('C') rather
+        // than 'K'.
+        let mut board = [[Piece::Empty; 8]; 8];
+        board[7][1] = Piece::King(WHITE);
+        board[7][0] = Piece::Rook(WHITE);
+        board[7][2] = Piece::Rook(WHITE);
+        board[0][4] = Piece::King(BLACK);
+        let white_castling = Castling::from_rook_files(Some(2), Some(0));
+
+        let game = ChessGame::new(board, WHITE, white_castling, Castling::None, None, 0, 1);
+        let fen = game.to_fen();
+
+        assert_eq!(fen, "4k3/8/8/8/8/8/8/RKR5 w CQ - 0 1");
+        assert_eq!(create_from_fen(&fen).unwrap(), game);
+    }
+
+    #[test]
+    fn to_fen_round_trips_endgame_position() {
+        let fen = "8/5k2/3p4/1p1Pp2p/pP2Pp1P/P4P1K/8/8 b - - 99 50";
+
+        let game = create_from_fen(fen).unwrap();
+
+        assert_eq!(game.to_fen(), fen);
+    }
+
+    #[test]
+    fn startpos_literal_is_the_standard_starting_position() {
+        let game = create_from_fen("startpos").unwrap();
+
+        assert_eq!(
+            game,
+            ChessGame::new(STARTING_BOARD, WHITE, STANDARD_BOTH, STANDARD_BOTH, None, 0, 1)
+        );
+    }
+
+    #[test]
+    fn position_only_fen_fills_in_defaults() {
+        let game = create_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+
+        assert_eq!(
+            game,
+            ChessGame::new(STARTING_BOARD, WHITE, Castling::None, Castling::None, None, 0, 1)
+        );
+    }
+
+    #[test]
+    fn extra_whitespace_between_fields_is_tolerated() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR  w   KQkq  -  0  1";
+
+        let game = create_from_fen(fen).unwrap();
+
+        assert_eq!(
+            game,
+            ChessGame::new(STARTING_BOARD, WHITE, STANDARD_BOTH, STANDARD_BOTH, None, 0, 1)
+        );
+    }
+
+    #[test]
+    fn display_matches_to_fen() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+        let game = create_from_fen(fen).unwrap();
+
+        assert_eq!(game.to_string(), game.to_fen());
+    }
 }
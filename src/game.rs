@@ -1,16 +1,24 @@
+use std::fmt;
+
+use crate::bitboard::{Bitboard, BitboardBoard};
 use crate::error::ErrorWrapper;
 
+pub type MailBoxBoard = [[Piece; 8]; 8];
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct ChessGame {
-    board: [[Piece; 8]; 8],
+    board: MailBoxBoard,
+    bitboards: BitboardBoard,
     turn: Color,
-    // white_castling: Castling,
-    // black_castling: Castling,
-    // en_passant: Option<(usize, usize)>,
-    // halfmove: u8,
-    // fullmove: u16,
+    white_castling: Castling,
+    black_castling: Castling,
+    en_passant: Option<(usize, usize)>,
+    halfmove: usize,
+    fullmove: usize,
+    hash: u64,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Piece {
     Pawn(Color),
     Knight(Color),
@@ -21,47 +29,172 @@ pub enum Piece {
     Empty,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Color {
     White,
     Black,
 }
 
+/// A side's castling rights. Kingside/queenside rights carry the file of
+/// the rook they apply to (0 = a-file, 7 = h-file) so that Chess960 / X-FEN
+/// starting positions, where the rook need not be on the a- or h-file, can
+/// be represented and later validated.
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Castling {
-    Both,
-    QueenSide,
-    KingSide,
+    None,
+    KingSide(usize),
+    QueenSide(usize),
+    Both {
+        kingside_rook_file: usize,
+        queenside_rook_file: usize,
+    },
 }
 
-fn fen_piece_to_piece(fen_piece: char) -> Piece {
-    let color = if fen_piece.is_uppercase() {
-        Color::White
-    } else {
-        Color::Black
+impl Castling {
+    pub fn from_rook_files(kingside: Option<usize>, queenside: Option<usize>) -> Castling {
+        match (kingside, queenside) {
+            (None, None) => Castling::None,
+            (Some(file), None) => Castling::KingSide(file),
+            (None, Some(file)) => Castling::QueenSide(file),
+            (Some(kingside_rook_file), Some(queenside_rook_file)) => Castling::Both {
+                kingside_rook_file,
+                queenside_rook_file,
+            },
+        }
+    }
+
+    pub fn kingside_rook_file(&self) -> Option<usize> {
+        match self {
+            Castling::KingSide(file) => Some(*file),
+            Castling::Both {
+                kingside_rook_file, ..
+            } => Some(*kingside_rook_file),
+            _ => None,
+        }
+    }
+
+    pub fn queenside_rook_file(&self) -> Option<usize> {
+        match self {
+            Castling::QueenSide(file) => Some(*file),
+            Castling::Both {
+                queenside_rook_file,
+                ..
+            } => Some(*queenside_rook_file),
+            _ => None,
+        }
+    }
+
+    /// Drops whichever side's right is tied to `file`, leaving the other
+    /// side's right (if any) untouched. Used to revoke a single side's
+    /// castling right once its rook has moved or been captured.
+    pub(crate) fn without_rook_file(self, file: usize) -> Castling {
+        Castling::from_rook_files(
+            self.kingside_rook_file().filter(|&f| f != file),
+            self.queenside_rook_file().filter(|&f| f != file),
+        )
+    }
+}
+
+/// Converts an algebraic square such as "e3" into `(file_index, rank_index)`,
+/// where `file_index` is 0 for the `a`-file and `rank_index` counts down from
+/// rank 8 (0) to rank 1 (7), matching the row order produced while parsing
+/// FEN piece placement.
+pub fn convert_chess_coordinates(square: &str) -> Result<(usize, usize), ErrorWrapper> {
+    let mut chars = square.chars();
+    let (Some(file), Some(rank), None) = (chars.next(), chars.next(), chars.next()) else {
+        return Err(ErrorWrapper::InvalidCoordinates);
+    };
+
+    if !('a'..='h').contains(&file) {
+        return Err(ErrorWrapper::InvalidCoordinates);
+    }
+
+    let rank = rank.to_digit(10).ok_or(ErrorWrapper::InvalidCoordinates)?;
+    if !(1..=8).contains(&rank) {
+        return Err(ErrorWrapper::InvalidCoordinates);
+    }
+
+    let file_index = file as usize - 'a' as usize;
+    let rank_index = 8 - rank as usize;
+
+    Ok((file_index, rank_index))
+}
+
+/// Converts `(file_index, rank_index)` coordinates, as produced by
+/// [`convert_chess_coordinates`], back into an algebraic square such as
+/// "e3".
+pub fn square_to_algebraic((file_index, rank_index): (usize, usize)) -> String {
+    let file = (b'a' + file_index as u8) as char;
+    let rank = 8 - rank_index;
+    format!("{file}{rank}")
+}
+
+fn piece_to_fen_char(piece: Piece) -> char {
+    let (letter, color) = match piece {
+        Piece::Pawn(color) => ('p', color),
+        Piece::Knight(color) => ('n', color),
+        Piece::Bishop(color) => ('b', color),
+        Piece::Rook(color) => ('r', color),
+        Piece::Queen(color) => ('q', color),
+        Piece::King(color) => ('k', color),
+        Piece::Empty => unreachable!("empty squares are run-length encoded, not emitted directly"),
     };
 
-    match fen_piece.to_ascii_lowercase() {
-        '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' => Piece::Empty,
-        'p' => Piece::Pawn(color),
-        'n' => Piece::Knight(color),
-        'b' => Piece::Bishop(color),
-        'r' => Piece::Rook(color),
-        'q' => Piece::Queen(color),
-        'k' => Piece::King(color),
-        _ => Piece::Empty,
+    if color == Color::White {
+        letter.to_ascii_uppercase()
+    } else {
+        letter
+    }
+}
+
+pub(crate) fn find_king(board: &MailBoxBoard, color: Color) -> Vec<(usize, usize)> {
+    let mut squares = Vec::new();
+    for (row_index, row) in board.iter().enumerate() {
+        for (col_index, piece) in row.iter().enumerate() {
+            if *piece == Piece::King(color) {
+                squares.push((row_index, col_index));
+            }
+        }
+    }
+    squares
+}
+
+pub(crate) fn home_rank(color: Color) -> usize {
+    match color {
+        Color::White => 7,
+        Color::Black => 0,
     }
 }
 
 impl ChessGame {
-    pub fn new() -> ChessGame {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        board: MailBoxBoard,
+        turn: Color,
+        white_castling: Castling,
+        black_castling: Castling,
+        en_passant: Option<(usize, usize)>,
+        halfmove: usize,
+        fullmove: usize,
+    ) -> ChessGame {
+        let bitboards = BitboardBoard::from_mailbox(&board);
+        let hash = crate::zobrist::compute_hash(
+            &board,
+            turn,
+            white_castling,
+            black_castling,
+            en_passant,
+        );
         ChessGame {
-            board: [[Piece::Empty; 8]; 8],
-            turn: Color::White,
-            // white_castling: Castling::Both,
-            // black_castling: Castling::Both,
-            // en_passant: None,
-            // halfmove: 1,
-            // fullmove: 0,
+            board,
+            bitboards,
+            turn,
+            white_castling,
+            black_castling,
+            en_passant,
+            halfmove,
+            fullmove,
+            hash,
         }
     }
 
@@ -69,154 +202,484 @@ impl ChessGame {
         self.turn = turn;
     }
 
+    /// Places a single piece at `(file, rank)`, matching the coordinate
+    /// order [`convert_chess_coordinates`] produces (so `self.board` is
+    /// indexed `[rank][file]`, not `[file][rank]`).
     pub fn set_piece(&mut self, piece: Piece, file: usize, rank: usize) {
-        self.board[file][rank] = piece;
+        self.board[rank][file] = piece;
+        self.bitboards = BitboardBoard::from_mailbox(&self.board);
+        self.hash = crate::zobrist::compute_hash(
+            &self.board,
+            self.turn,
+            self.white_castling,
+            self.black_castling,
+            self.en_passant,
+        );
     }
 
-    pub fn create_from_fen(fen: &str) -> Result<ChessGame, ErrorWrapper> {
-        let mut new_game = ChessGame::new();
-        let [piece_placement, active_color, _castling] =
-            fen.split(" ").collect::<Vec<&str>>()[0..3]
-        else {
-            return Err(ErrorWrapper::InvalidFen);
-        };
+    pub fn board(&self) -> &MailBoxBoard {
+        &self.board
+    }
 
-        let color = match active_color {
-            "w" => Color::White,
-            "b" => Color::Black,
-            _ => return Err(ErrorWrapper::InvalidFen),
-        };
-        new_game.set_turn(color);
-
-        let mut file = 0;
-        let mut rank = 0;
-        for piece in piece_placement.chars() {
-            if piece == '/' {
-                file += 1;
-                rank = 0;
-                continue;
-            }
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    /// The occupancy bitboard for every piece of the given type and color
+    /// (e.g. `Piece::Rook(Color::Black)` yields only the black rooks).
+    pub fn pieces(&self, piece: Piece) -> Bitboard {
+        self.bitboards.pieces(piece)
+    }
+
+    /// The occupancy bitboard for every piece belonging to `color`.
+    pub fn color(&self, color: Color) -> Bitboard {
+        self.bitboards.color(color)
+    }
+
+    /// The occupancy bitboard for every occupied square on the board.
+    pub fn occupancy(&self) -> Bitboard {
+        self.bitboards.occupancy()
+    }
 
-            if let Some(empty_squares) = piece.to_digit(10) {
-                let empty_squares = empty_squares as usize;
+    pub fn castling(&self, color: Color) -> Castling {
+        match color {
+            Color::White => self.white_castling,
+            Color::Black => self.black_castling,
+        }
+    }
+
+    pub fn en_passant(&self) -> Option<(usize, usize)> {
+        self.en_passant
+    }
+
+    pub fn halfmove(&self) -> usize {
+        self.halfmove
+    }
+
+    pub fn fullmove(&self) -> usize {
+        self.fullmove
+    }
+
+    /// The Zobrist hash of this position, see [`crate::zobrist`].
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
 
-                for i in 0..empty_squares {
-                    new_game.set_piece(Piece::Empty, file, rank + i)
+    /// Overwrites the stored hash. Used by [`crate::movegen::make_move`] to
+    /// install its incrementally-updated hash. The [`ChessGame::new`] call
+    /// that builds the new position has already run
+    /// [`crate::zobrist::compute_hash`] by then, so every move still pays
+    /// for a full recomputation, whose result this replaces.
+    pub(crate) fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+    }
+
+    /// Checks that the position is legal: exactly one king per side, no
+    /// pawns on the back ranks, the kings are not adjacent, neither side
+    /// has more pieces than the rules allow, and any recorded castling
+    /// right is backed by a king and rook that are actually on their home
+    /// squares.
+    pub fn validate(&self) -> Result<(), ErrorWrapper> {
+        let white_kings = find_king(&self.board, Color::White);
+        let black_kings = find_king(&self.board, Color::Black);
+
+        match white_kings.len() {
+            0 => return Err(ErrorWrapper::MissingKing(Color::White)),
+            1 => {}
+            _ => return Err(ErrorWrapper::MultipleKings(Color::White)),
+        }
+
+        match black_kings.len() {
+            0 => return Err(ErrorWrapper::MissingKing(Color::Black)),
+            1 => {}
+            _ => return Err(ErrorWrapper::MultipleKings(Color::Black)),
+        }
+
+        let (white_row, white_col) = white_kings[0];
+        let (black_row, black_col) = black_kings[0];
+        let row_distance = white_row.abs_diff(black_row);
+        let col_distance = white_col.abs_diff(black_col);
+        if row_distance <= 1 && col_distance <= 1 {
+            return Err(ErrorWrapper::KingsAdjacent);
+        }
+
+        if self.board[0].iter().any(|p| matches!(p, Piece::Pawn(_)))
+            || self.board[7].iter().any(|p| matches!(p, Piece::Pawn(_)))
+        {
+            return Err(ErrorWrapper::PawnOnBackRank);
+        }
+
+        for color in [Color::White, Color::Black] {
+            let mut pawn_count = 0;
+            let mut piece_count = 0;
+            for row in &self.board {
+                for piece in row {
+                    if matches!(piece, Piece::Empty) {
+                        continue;
+                    }
+                    let piece_color = match piece {
+                        Piece::Pawn(c)
+                        | Piece::Knight(c)
+                        | Piece::Bishop(c)
+                        | Piece::Rook(c)
+                        | Piece::Queen(c)
+                        | Piece::King(c) => *c,
+                        Piece::Empty => continue,
+                    };
+                    if piece_color != color {
+                        continue;
+                    }
+                    piece_count += 1;
+                    if matches!(piece, Piece::Pawn(_)) {
+                        pawn_count += 1;
+                    }
                 }
+            }
 
-                rank += empty_squares;
-                continue;
+            if pawn_count > 8 {
+                return Err(ErrorWrapper::TooManyPawns(color));
+            }
+            if piece_count > 16 {
+                return Err(ErrorWrapper::TooManyPieces(color));
             }
+        }
+
+        self.validate_castling(Color::White, self.white_castling)?;
+        self.validate_castling(Color::Black, self.black_castling)?;
+
+        Ok(())
+    }
+
+    fn validate_castling(&self, color: Color, castling: Castling) -> Result<(), ErrorWrapper> {
+        if castling == Castling::None {
+            return Ok(());
+        }
 
-            let piece = fen_piece_to_piece(piece);
+        let home = home_rank(color);
+        if !self.board[home].contains(&Piece::King(color)) {
+            return Err(ErrorWrapper::InvalidCastlingRights);
+        }
 
-            new_game.set_piece(piece, file, rank);
+        if let Some(file) = castling.kingside_rook_file() {
+            if self.board[home][file] != Piece::Rook(color) {
+                return Err(ErrorWrapper::InvalidCastlingRights);
+            }
+        }
 
-            rank += 1
+        if let Some(file) = castling.queenside_rook_file() {
+            if self.board[home][file] != Piece::Rook(color) {
+                return Err(ErrorWrapper::InvalidCastlingRights);
+            }
         }
 
-        Ok(new_game)
+        Ok(())
+    }
+
+    /// Serializes the position back into a FEN string. Round-tripping a FEN
+    /// through [`crate::fen_parser::create_from_fen`] and `to_fen` yields
+    /// the original string when it is already in the form `to_fen` writes:
+    /// all six fields, single spaces, and `K`/`Q` rather than a file letter
+    /// for h-/a-file rooks. Other spellings come back normalized (e.g.
+    /// `HAha` becomes `KQkq`).
+    pub fn to_fen(&self) -> String {
+        let placement = self
+            .board
+            .iter()
+            .map(|row| {
+                let mut rank = String::new();
+                let mut empty_run = 0;
+                for piece in row {
+                    if *piece == Piece::Empty {
+                        empty_run += 1;
+                        continue;
+                    }
+                    if empty_run > 0 {
+                        rank.push_str(&empty_run.to_string());
+                        empty_run = 0;
+                    }
+                    rank.push(piece_to_fen_char(*piece));
+                }
+                if empty_run > 0 {
+                    rank.push_str(&empty_run.to_string());
+                }
+                rank
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let active_color = match self.turn {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+
+        let castling = castling_to_fen(self.white_castling, self.black_castling);
+
+        let en_passant = match self.en_passant {
+            Some(square) => square_to_algebraic(square),
+            None => "-".to_string(),
+        };
+
+        format!(
+            "{placement} {active_color} {castling} {en_passant} {} {}",
+            self.halfmove, self.fullmove
+        )
+    }
+}
+
+/// Renders a rook file as its FEN castling letter: the standard `K`/`Q`
+/// spelling when the rook sits on the h-/a-file respectively, and the
+/// X-FEN file letter otherwise, lower-cased for black.
+fn castling_letter(file: usize, standard_file: usize, standard_letter: char, color: Color) -> char {
+    let letter = if file == standard_file {
+        standard_letter
+    } else {
+        (b'A' + file as u8) as char
+    };
+
+    if color == Color::White {
+        letter
+    } else {
+        letter.to_ascii_lowercase()
+    }
+}
+
+fn append_castling(castling: Castling, color: Color, rights: &mut String) {
+    if let Some(file) = castling.kingside_rook_file() {
+        rights.push(castling_letter(file, 7, 'K', color));
+    }
+    if let Some(file) = castling.queenside_rook_file() {
+        rights.push(castling_letter(file, 0, 'Q', color));
+    }
+}
+
+fn castling_to_fen(white: Castling, black: Castling) -> String {
+    let mut rights = String::new();
+    append_castling(white, Color::White, &mut rights);
+    append_castling(black, Color::Black, &mut rights);
+
+    if rights.is_empty() {
+        "-".to_string()
+    } else {
+        rights
+    }
+}
+
+impl fmt::Display for ChessGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_fen())
     }
 }
 
 #[cfg(test)]
-pub mod tests {
-    use crate::game::{Color, Piece};
+mod tests {
+    use crate::error::ErrorWrapper;
+    use crate::test_boards::{BOARD2, STARTING_BOARD};
 
-    use super::ChessGame;
+    use super::{convert_chess_coordinates, Castling, ChessGame, Color, Piece};
 
-    const STARTING_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    #[test]
+    fn converts_algebraic_square_to_coordinates() {
+        assert_eq!(convert_chess_coordinates("e3").unwrap(), (4, 5));
+        assert_eq!(convert_chess_coordinates("a8").unwrap(), (0, 0));
+        assert_eq!(convert_chess_coordinates("h1").unwrap(), (7, 7));
+    }
 
     #[test]
-    pub fn new_game_from_starting_position_fen() {
-        let game = ChessGame::create_from_fen(STARTING_POSITION).unwrap();
+    fn set_piece_uses_the_same_coordinate_order_as_convert_chess_coordinates() {
+        let mut game = ChessGame::new(
+            [[Piece::Empty; 8]; 8],
+            Color::White,
+            Castling::None,
+            Castling::None,
+            None,
+            0,
+            1,
+        );
+        let (file, rank) = convert_chess_coordinates("e3").unwrap();
+
+        game.set_piece(Piece::Queen(Color::White), file, rank);
 
-        let black = Color::Black;
-        let white = Color::White;
+        assert_eq!(game.board()[rank][file], Piece::Queen(Color::White));
+    }
+
+    #[test]
+    fn bitboards_mirror_the_starting_position() {
+        let game = ChessGame::new(
+            STARTING_BOARD,
+            Color::White,
+            Castling::Both { kingside_rook_file: 7, queenside_rook_file: 0 },
+            Castling::Both { kingside_rook_file: 7, queenside_rook_file: 0 },
+            None,
+            0,
+            1,
+        );
+
+        assert_eq!(game.occupancy().count(), 32);
+        assert_eq!(game.color(Color::White).count(), 16);
+        assert_eq!(game.pieces(Piece::Rook(Color::Black)).count(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_square() {
         assert_eq!(
-            game.board,
-            [
-                [
-                    Piece::Rook(black),
-                    Piece::Knight(black),
-                    Piece::Bishop(black),
-                    Piece::Queen(black),
-                    Piece::King(black),
-                    Piece::Bishop(black),
-                    Piece::Knight(black),
-                    Piece::Rook(black),
-                ],
-                [
-                    Piece::Pawn(black),
-                    Piece::Pawn(black),
-                    Piece::Pawn(black),
-                    Piece::Pawn(black),
-                    Piece::Pawn(black),
-                    Piece::Pawn(black),
-                    Piece::Pawn(black),
-                    Piece::Pawn(black),
-                ],
-                [
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                ],
-                [
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                ],
-                [
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                ],
-                [
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                    Piece::Empty,
-                ],
-                [
-                    Piece::Pawn(white),
-                    Piece::Pawn(white),
-                    Piece::Pawn(white),
-                    Piece::Pawn(white),
-                    Piece::Pawn(white),
-                    Piece::Pawn(white),
-                    Piece::Pawn(white),
-                    Piece::Pawn(white),
-                ],
-                [
-                    Piece::Rook(white),
-                    Piece::Knight(white),
-                    Piece::Bishop(white),
-                    Piece::Queen(white),
-                    Piece::King(white),
-                    Piece::Bishop(white),
-                    Piece::Knight(white),
-                    Piece::Rook(white),
-                ],
-            ]
-        )
+            convert_chess_coordinates("o1"),
+            Err(ErrorWrapper::InvalidCoordinates)
+        );
+        assert_eq!(
+            convert_chess_coordinates("a9"),
+            Err(ErrorWrapper::InvalidCoordinates)
+        );
+    }
+
+    #[test]
+    fn starting_position_is_valid() {
+        let game = ChessGame::new(
+            STARTING_BOARD,
+            Color::White,
+            Castling::Both { kingside_rook_file: 7, queenside_rook_file: 0 },
+            Castling::Both { kingside_rook_file: 7, queenside_rook_file: 0 },
+            None,
+            0,
+            1,
+        );
+
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn endgame_position_is_valid() {
+        let game = ChessGame::new(
+            BOARD2,
+            Color::Black,
+            Castling::None,
+            Castling::None,
+            None,
+            99,
+            50,
+        );
+
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn missing_king_is_invalid() {
+        let mut board = STARTING_BOARD;
+        board[7][4] = super::Piece::Empty;
+        let game = ChessGame::new(board, Color::White, Castling::None, Castling::None, None, 0, 1);
+
+        assert_eq!(game.validate(), Err(ErrorWrapper::MissingKing(Color::White)));
+    }
+
+    #[test]
+    fn adjacent_kings_are_invalid() {
+        let mut board = STARTING_BOARD;
+        board[0][4] = Piece::Empty;
+        board[7][4] = Piece::Empty;
+        board[3][4] = Piece::King(Color::White);
+        board[4][4] = Piece::King(Color::Black);
+        let game = ChessGame::new(board, Color::White, Castling::None, Castling::None, None, 0, 1);
+
+        assert_eq!(game.validate(), Err(ErrorWrapper::KingsAdjacent));
+    }
+
+    #[test]
+    fn castling_rights_without_rook_are_invalid() {
+        let mut board = STARTING_BOARD;
+        board[7][7] = super::Piece::Empty;
+        let game = ChessGame::new(
+            board,
+            Color::White,
+            Castling::Both { kingside_rook_file: 7, queenside_rook_file: 0 },
+            Castling::None,
+            None,
+            0,
+            1,
+        );
+
+        assert_eq!(game.validate(), Err(ErrorWrapper::InvalidCastlingRights));
+    }
+
+    #[test]
+    fn chess960_castling_rights_use_the_actual_rook_files() {
+        let mut board = [[Piece::Empty; 8]; 8];
+        board[7][4] = Piece::King(Color::White);
+        board[7][1] = Piece::Rook(Color::White);
+        board[7][5] = Piece::Rook(Color::White);
+        board[0][4] = Piece::King(Color::Black);
+
+        let game = ChessGame::new(
+            board,
+            Color::White,
+            Castling::Both {
+                kingside_rook_file: 5,
+                queenside_rook_file: 1,
+            },
+            Castling::None,
+            None,
+            0,
+            1,
+        );
+
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn constructing_the_same_position_twice_yields_the_same_hash() {
+        let first = ChessGame::new(
+            STARTING_BOARD,
+            Color::White,
+            Castling::Both {
+                kingside_rook_file: 7,
+                queenside_rook_file: 0,
+            },
+            Castling::Both {
+                kingside_rook_file: 7,
+                queenside_rook_file: 0,
+            },
+            None,
+            0,
+            1,
+        );
+        let second = ChessGame::new(
+            STARTING_BOARD,
+            Color::White,
+            Castling::Both {
+                kingside_rook_file: 7,
+                queenside_rook_file: 0,
+            },
+            Castling::Both {
+                kingside_rook_file: 7,
+                queenside_rook_file: 0,
+            },
+            None,
+            0,
+            1,
+        );
+
+        assert_eq!(first.hash(), second.hash());
+    }
+
+    #[test]
+    fn flipping_the_side_to_move_changes_the_hash() {
+        let white_to_move = ChessGame::new(
+            STARTING_BOARD,
+            Color::White,
+            Castling::None,
+            Castling::None,
+            None,
+            0,
+            1,
+        );
+        let black_to_move = ChessGame::new(
+            STARTING_BOARD,
+            Color::Black,
+            Castling::None,
+            Castling::None,
+            None,
+            0,
+            1,
+        );
+
+        assert_ne!(white_to_move.hash(), black_to_move.hash());
     }
 }
@@ -0,0 +1,10 @@
+pub mod bitboard;
+pub mod builder;
+pub mod error;
+pub mod fen_parser;
+pub mod game;
+pub mod movegen;
+pub mod zobrist;
+
+#[cfg(test)]
+mod test_boards;
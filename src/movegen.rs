@@ -0,0 +1,961 @@
+use crate::bitboard::{Bitboard, FILES, RANKS};
+use crate::error::ErrorWrapper;
+use crate::game::{home_rank, Castling, ChessGame, Color, MailBoxBoard, Piece};
+use crate::zobrist;
+
+/// A single chess move. Squares are `(row, col)` pairs matching the
+/// row-major layout of [`MailBoxBoard`] (row 0 is rank 8, col 0 is the
+/// a-file). `castling_rook_from` is the file of the rook that rides along
+/// on a castling move (so the board knows which rook to relocate even on a
+/// Chess960 setup where it isn't on the a- or h-file), and is `None` for
+/// every other move.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+    pub promotion: Option<Piece>,
+    pub castling_rook_from: Option<usize>,
+}
+
+const ROOK_DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const BISHOP_DIRECTIONS: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+const KNIGHT_OFFSETS: [(isize, isize); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+const KING_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+const PROMOTION_PIECES: [fn(Color) -> Piece; 4] =
+    [Piece::Queen, Piece::Rook, Piece::Bishop, Piece::Knight];
+
+fn on_board(row: isize, col: isize) -> bool {
+    (0..8).contains(&row) && (0..8).contains(&col)
+}
+
+fn piece_color(piece: Piece) -> Option<Color> {
+    match piece {
+        Piece::Pawn(color)
+        | Piece::Knight(color)
+        | Piece::Bishop(color)
+        | Piece::Rook(color)
+        | Piece::Queen(color)
+        | Piece::King(color) => Some(color),
+        Piece::Empty => None,
+    }
+}
+
+fn opposite(color: Color) -> Color {
+    match color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    }
+}
+
+fn pawn_forward(color: Color) -> isize {
+    match color {
+        Color::White => -1,
+        Color::Black => 1,
+    }
+}
+
+fn pawn_start_row(color: Color) -> usize {
+    match color {
+        Color::White => 6,
+        Color::Black => 1,
+    }
+}
+
+fn promotion_row(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 7,
+    }
+}
+
+/// Converts the `(file, rank)` tuple stored on [`ChessGame`] into the
+/// `(row, col)` order used by [`Move`].
+fn en_passant_as_row_col(en_passant: Option<(usize, usize)>) -> Option<(usize, usize)> {
+    en_passant.map(|(file, rank)| (rank, file))
+}
+
+fn sliding_moves(
+    board: &MailBoxBoard,
+    from: (usize, usize),
+    directions: &[(isize, isize)],
+    color: Color,
+    moves: &mut Vec<Move>,
+) {
+    for &(dr, dc) in directions {
+        let mut row = from.0 as isize;
+        let mut col = from.1 as isize;
+        loop {
+            row += dr;
+            col += dc;
+            if !on_board(row, col) {
+                break;
+            }
+            let to = (row as usize, col as usize);
+            let target = board[to.0][to.1];
+            match piece_color(target) {
+                Some(c) if c == color => break,
+                Some(_) => {
+                    moves.push(Move {
+                        from,
+                        to,
+                        promotion: None,
+                        castling_rook_from: None,
+                    });
+                    break;
+                }
+                None => moves.push(Move {
+                    from,
+                    to,
+                    promotion: None,
+                    castling_rook_from: None,
+                }),
+            }
+        }
+    }
+}
+
+fn stepping_moves(
+    board: &MailBoxBoard,
+    from: (usize, usize),
+    offsets: &[(isize, isize)],
+    color: Color,
+    moves: &mut Vec<Move>,
+) {
+    for &(dr, dc) in offsets {
+        let row = from.0 as isize + dr;
+        let col = from.1 as isize + dc;
+        if !on_board(row, col) {
+            continue;
+        }
+        let to = (row as usize, col as usize);
+        if piece_color(board[to.0][to.1]) == Some(color) {
+            continue;
+        }
+        moves.push(Move {
+            from,
+            to,
+            promotion: None,
+            castling_rook_from: None,
+        });
+    }
+}
+
+fn push_pawn_move(moves: &mut Vec<Move>, from: (usize, usize), to: (usize, usize), color: Color) {
+    if to.0 == promotion_row(color) {
+        for make_piece in PROMOTION_PIECES {
+            moves.push(Move {
+                from,
+                to,
+                promotion: Some(make_piece(color)),
+                castling_rook_from: None,
+            });
+        }
+    } else {
+        moves.push(Move {
+            from,
+            to,
+            promotion: None,
+            castling_rook_from: None,
+        });
+    }
+}
+
+fn pawn_moves(
+    board: &MailBoxBoard,
+    from: (usize, usize),
+    color: Color,
+    en_passant: Option<(usize, usize)>,
+    moves: &mut Vec<Move>,
+) {
+    let forward = pawn_forward(color);
+    let single_row = from.0 as isize + forward;
+    if !on_board(single_row, from.1 as isize) {
+        return;
+    }
+    let single_row = single_row as usize;
+
+    if board[single_row][from.1] == Piece::Empty {
+        push_pawn_move(moves, from, (single_row, from.1), color);
+
+        if from.0 == pawn_start_row(color) {
+            let double_row = (from.0 as isize + forward * 2) as usize;
+            if board[double_row][from.1] == Piece::Empty {
+                moves.push(Move {
+                    from,
+                    to: (double_row, from.1),
+                    promotion: None,
+                    castling_rook_from: None,
+                });
+            }
+        }
+    }
+
+    let en_passant = en_passant_as_row_col(en_passant);
+    for dc in [-1isize, 1] {
+        let col = from.1 as isize + dc;
+        if !on_board(single_row as isize, col) {
+            continue;
+        }
+        let to = (single_row, col as usize);
+        if piece_color(board[to.0][to.1]) == Some(opposite(color)) {
+            push_pawn_move(moves, from, to, color);
+        } else if Some(to) == en_passant {
+            moves.push(Move {
+                from,
+                to,
+                promotion: None,
+                castling_rook_from: None,
+            });
+        }
+    }
+}
+
+/// All pseudo-legal moves for the side to move, ignoring whether they
+/// leave that side's own king in check.
+pub fn pseudo_legal_moves(game: &ChessGame) -> Vec<Move> {
+    let board = game.board();
+    let color = game.turn();
+    let en_passant = game.en_passant();
+    let mut moves = Vec::new();
+
+    for row in 0..8 {
+        for col in 0..8 {
+            let piece = board[row][col];
+            if piece_color(piece) != Some(color) {
+                continue;
+            }
+            let from = (row, col);
+            match piece {
+                Piece::Pawn(_) => pawn_moves(board, from, color, en_passant, &mut moves),
+                Piece::Knight(_) => stepping_moves(board, from, &KNIGHT_OFFSETS, color, &mut moves),
+                Piece::King(_) => stepping_moves(board, from, &KING_OFFSETS, color, &mut moves),
+                Piece::Bishop(_) => sliding_moves(board, from, &BISHOP_DIRECTIONS, color, &mut moves),
+                Piece::Rook(_) => sliding_moves(board, from, &ROOK_DIRECTIONS, color, &mut moves),
+                Piece::Queen(_) => {
+                    sliding_moves(board, from, &ROOK_DIRECTIONS, color, &mut moves);
+                    sliding_moves(board, from, &BISHOP_DIRECTIONS, color, &mut moves);
+                }
+                Piece::Empty => {}
+            }
+        }
+    }
+
+    castling_moves(board, game.occupancy(), color, game.castling(color), &mut moves);
+
+    moves
+}
+
+/// The king's and rook's destination files for castling kingside/queenside,
+/// per the standard Chess960/UCI convention: the king always ends on the
+/// g-file (kingside) or c-file (queenside), and the rook always ends on the
+/// f-file or d-file respectively, regardless of where either started.
+const CASTLE_KING_DEST_KINGSIDE: usize = 6;
+const CASTLE_ROOK_DEST_KINGSIDE: usize = 5;
+const CASTLE_KING_DEST_QUEENSIDE: usize = 2;
+const CASTLE_ROOK_DEST_QUEENSIDE: usize = 3;
+
+/// The bitboard mask of every square on `rank` whose file falls in
+/// `lo..=hi`, built from [`RANKS`] and [`FILES`].
+fn rank_file_range_mask(rank: usize, lo: usize, hi: usize) -> Bitboard {
+    let files_mask = FILES[lo..=hi]
+        .iter()
+        .fold(Bitboard::EMPTY, |mask, &file| mask | file);
+    files_mask & RANKS[rank]
+}
+
+/// Whether every square the king or rook needs to pass through or land on,
+/// other than their own current squares, is empty. Checked as a single
+/// bitwise AND against `occupancy` rather than a per-square board scan.
+fn castling_path_is_clear(
+    occupancy: Bitboard,
+    rank: usize,
+    king_file: usize,
+    king_dest: usize,
+    rook_file: usize,
+    rook_dest: usize,
+) -> bool {
+    let lo = king_file.min(king_dest).min(rook_file).min(rook_dest);
+    let hi = king_file.max(king_dest).max(rook_file).max(rook_dest);
+
+    let mut must_be_empty = rank_file_range_mask(rank, lo, hi);
+    must_be_empty.clear(rank * 8 + king_file);
+    must_be_empty.clear(rank * 8 + rook_file);
+
+    (must_be_empty & occupancy) == Bitboard::EMPTY
+}
+
+/// Appends the castling move for one side (kingside or queenside) if the
+/// rook is still in place, the squares between king and rook are empty, and
+/// the king doesn't start, pass through, or land on an attacked square.
+#[allow(clippy::too_many_arguments)]
+fn try_castling_move(
+    board: &MailBoxBoard,
+    occupancy: Bitboard,
+    rank: usize,
+    king_file: usize,
+    rook_file: usize,
+    king_dest: usize,
+    rook_dest: usize,
+    color: Color,
+    moves: &mut Vec<Move>,
+) {
+    if board[rank][rook_file] != Piece::Rook(color) {
+        return;
+    }
+    if !castling_path_is_clear(occupancy, rank, king_file, king_dest, rook_file, rook_dest) {
+        return;
+    }
+
+    let (lo, hi) = (king_file.min(king_dest), king_file.max(king_dest));
+    if (lo..=hi).any(|file| is_square_attacked(board, (rank, file), opposite(color))) {
+        return;
+    }
+
+    moves.push(Move {
+        from: (rank, king_file),
+        to: (rank, king_dest),
+        promotion: None,
+        castling_rook_from: Some(rook_file),
+    });
+}
+
+/// Appends `color`'s legal castling moves, if any, given its current rights.
+fn castling_moves(
+    board: &MailBoxBoard,
+    occupancy: Bitboard,
+    color: Color,
+    castling: Castling,
+    moves: &mut Vec<Move>,
+) {
+    let rank = home_rank(color);
+    let Some((king_row, king_file)) = find_king_square(board, color) else {
+        return;
+    };
+    if king_row != rank || is_square_attacked(board, (rank, king_file), opposite(color)) {
+        return;
+    }
+
+    if let Some(rook_file) = castling.kingside_rook_file() {
+        try_castling_move(
+            board,
+            occupancy,
+            rank,
+            king_file,
+            rook_file,
+            CASTLE_KING_DEST_KINGSIDE,
+            CASTLE_ROOK_DEST_KINGSIDE,
+            color,
+            moves,
+        );
+    }
+    if let Some(rook_file) = castling.queenside_rook_file() {
+        try_castling_move(
+            board,
+            occupancy,
+            rank,
+            king_file,
+            rook_file,
+            CASTLE_KING_DEST_QUEENSIDE,
+            CASTLE_ROOK_DEST_QUEENSIDE,
+            color,
+            moves,
+        );
+    }
+}
+
+fn find_king_square(board: &MailBoxBoard, color: Color) -> Option<(usize, usize)> {
+    for (row, squares) in board.iter().enumerate() {
+        for (col, piece) in squares.iter().enumerate() {
+            if *piece == Piece::King(color) {
+                return Some((row, col));
+            }
+        }
+    }
+    None
+}
+
+fn ray_attacks(
+    board: &MailBoxBoard,
+    from: (usize, usize),
+    directions: &[(isize, isize)],
+    targets: &[Piece],
+) -> bool {
+    for &(dr, dc) in directions {
+        let mut row = from.0 as isize;
+        let mut col = from.1 as isize;
+        loop {
+            row += dr;
+            col += dc;
+            if !on_board(row, col) {
+                break;
+            }
+            let piece = board[row as usize][col as usize];
+            if piece == Piece::Empty {
+                continue;
+            }
+            if targets.contains(&piece) {
+                return true;
+            }
+            break;
+        }
+    }
+    false
+}
+
+/// Whether `attacker` has a piece that attacks `square` on `board`.
+fn is_square_attacked(board: &MailBoxBoard, square: (usize, usize), attacker: Color) -> bool {
+    for &(dr, dc) in &KNIGHT_OFFSETS {
+        let row = square.0 as isize + dr;
+        let col = square.1 as isize + dc;
+        if on_board(row, col) && board[row as usize][col as usize] == Piece::Knight(attacker) {
+            return true;
+        }
+    }
+
+    for &(dr, dc) in &KING_OFFSETS {
+        let row = square.0 as isize + dr;
+        let col = square.1 as isize + dc;
+        if on_board(row, col) && board[row as usize][col as usize] == Piece::King(attacker) {
+            return true;
+        }
+    }
+
+    let forward = pawn_forward(attacker);
+    for dc in [-1isize, 1] {
+        let row = square.0 as isize - forward;
+        let col = square.1 as isize + dc;
+        if on_board(row, col) && board[row as usize][col as usize] == Piece::Pawn(attacker) {
+            return true;
+        }
+    }
+
+    if ray_attacks(
+        board,
+        square,
+        &ROOK_DIRECTIONS,
+        &[Piece::Rook(attacker), Piece::Queen(attacker)],
+    ) {
+        return true;
+    }
+
+    ray_attacks(
+        board,
+        square,
+        &BISHOP_DIRECTIONS,
+        &[Piece::Bishop(attacker), Piece::Queen(attacker)],
+    )
+}
+
+/// The rook's destination file for a castling move that lands the king on
+/// `king_dest_file`, the counterpart of [`CASTLE_KING_DEST_KINGSIDE`]/
+/// [`CASTLE_KING_DEST_QUEENSIDE`]. `None` if `king_dest_file` isn't a file a
+/// castling move can ever land a king on, which means `mv.castling_rook_from`
+/// was set on a `Move` that isn't actually a castling move.
+fn rook_destination_file(king_dest_file: usize) -> Option<usize> {
+    match king_dest_file {
+        CASTLE_KING_DEST_KINGSIDE => Some(CASTLE_ROOK_DEST_KINGSIDE),
+        CASTLE_KING_DEST_QUEENSIDE => Some(CASTLE_ROOK_DEST_QUEENSIDE),
+        _ => None,
+    }
+}
+
+/// Applies `mv` to a bare board, including sweeping the captured pawn off
+/// the board for an en-passant capture and relocating the rook for a
+/// castling move. Errors with [`ErrorWrapper::InvalidMove`] if
+/// `mv.castling_rook_from` is set but `mv.to` isn't a valid castling
+/// destination for the king.
+fn apply_move_to_board(board: &MailBoxBoard, mv: Move) -> Result<MailBoxBoard, ErrorWrapper> {
+    let mut board = *board;
+    let moving_piece = board[mv.from.0][mv.from.1];
+    let is_en_passant_capture = matches!(moving_piece, Piece::Pawn(_))
+        && mv.from.1 != mv.to.1
+        && board[mv.to.0][mv.to.1] == Piece::Empty;
+    // Read the rook off before the king's own move below can overwrite its
+    // square, which can happen on a Chess960 board where the rook already
+    // sits on the king's destination file.
+    let castling_rook = mv
+        .castling_rook_from
+        .map(|rook_from_file| (rook_from_file, board[mv.from.0][rook_from_file]));
+
+    board[mv.from.0][mv.from.1] = Piece::Empty;
+    board[mv.to.0][mv.to.1] = mv.promotion.unwrap_or(moving_piece);
+
+    if is_en_passant_capture {
+        board[mv.from.0][mv.to.1] = Piece::Empty;
+    }
+
+    if let Some((rook_from_file, rook)) = castling_rook {
+        let rank = mv.from.0;
+        let rook_to_file = rook_destination_file(mv.to.1).ok_or(ErrorWrapper::InvalidMove)?;
+        // Leave the rook's old square alone when the king has just landed on it.
+        if rook_from_file != mv.to.1 {
+            board[rank][rook_from_file] = Piece::Empty;
+        }
+        board[rank][rook_to_file] = rook;
+    }
+
+    Ok(board)
+}
+
+/// Filters pseudo-legal moves down to those that don't leave the mover's
+/// own king in check.
+pub fn legal_moves(game: &ChessGame) -> Vec<Move> {
+    let color = game.turn();
+    let board = game.board();
+
+    pseudo_legal_moves(game)
+        .into_iter()
+        .filter(|&mv| {
+            let resulting_board = apply_move_to_board(board, mv)
+                .expect("pseudo_legal_moves only ever produces well-formed moves");
+            match find_king_square(&resulting_board, color) {
+                Some(king_square) => {
+                    !is_square_attacked(&resulting_board, king_square, opposite(color))
+                }
+                None => false,
+            }
+        })
+        .collect()
+}
+
+/// Drops both of `color`'s castling rights if `piece` is `color`'s king
+/// (moving, including by castling, or being captured), or the specific
+/// side's right if `piece` is `color`'s rook standing on its recorded
+/// castling square (`square`, which must be on `color`'s home rank - a rook
+/// that has already wandered elsewhere no longer carries any right to lose).
+fn revoke_castling_right(
+    castling: Castling,
+    piece: Piece,
+    color: Color,
+    square: (usize, usize),
+) -> Castling {
+    match piece {
+        Piece::King(piece_color) if piece_color == color => Castling::None,
+        Piece::Rook(piece_color) if piece_color == color && square.0 == home_rank(color) => {
+            castling.without_rook_file(square.1)
+        }
+        _ => castling,
+    }
+}
+
+/// Plays `mv` and returns the resulting position: the board updates, the
+/// side to move flips, the en-passant square is set behind a fresh double
+/// push (and cleared otherwise), the move counters advance as FEN defines
+/// them, and a side's castling rights are dropped the moment its king
+/// moves (including by castling itself) or a rook moves off or is captured
+/// on its recorded file. Errors with [`ErrorWrapper::InvalidMove`] if `mv`
+/// claims to be a castling move but doesn't land the king on a valid
+/// castling destination file - `Move`'s fields are all public, so this can
+/// happen on a hand-built `Move` even though every `Move` produced by
+/// [`legal_moves`] is always well-formed.
+pub fn make_move(game: &ChessGame, mv: Move) -> Result<ChessGame, ErrorWrapper> {
+    let color = game.turn();
+    let board = game.board();
+    let moving_piece = board[mv.from.0][mv.from.1];
+    let captured_piece = board[mv.to.0][mv.to.1];
+    // A castling king may land on its own rook's square; that is not a capture.
+    let is_capture = captured_piece != Piece::Empty && mv.castling_rook_from.is_none();
+    let is_pawn_move = matches!(moving_piece, Piece::Pawn(_));
+    let is_en_passant_capture =
+        is_pawn_move && mv.from.1 != mv.to.1 && captured_piece == Piece::Empty;
+
+    let new_board = apply_move_to_board(board, mv)?;
+
+    let en_passant = if is_pawn_move && mv.from.0.abs_diff(mv.to.0) == 2 {
+        let skipped_row = (mv.from.0 + mv.to.0) / 2;
+        Some((mv.from.1, skipped_row))
+    } else {
+        None
+    };
+
+    let halfmove = if is_pawn_move || is_capture {
+        0
+    } else {
+        game.halfmove() + 1
+    };
+
+    let fullmove = match color {
+        Color::Black => game.fullmove() + 1,
+        Color::White => game.fullmove(),
+    };
+
+    let old_white_castling = game.castling(Color::White);
+    let old_black_castling = game.castling(Color::Black);
+    let mut white_castling =
+        revoke_castling_right(old_white_castling, moving_piece, Color::White, mv.from);
+    let mut black_castling =
+        revoke_castling_right(old_black_castling, moving_piece, Color::Black, mv.from);
+    if is_capture {
+        white_castling = revoke_castling_right(white_castling, captured_piece, Color::White, mv.to);
+        black_castling = revoke_castling_right(black_castling, captured_piece, Color::Black, mv.to);
+    }
+
+    let mut hash = game.hash();
+    hash ^= zobrist::piece_square_key(moving_piece, zobrist::square_index(mv.from.0, mv.from.1));
+    hash ^= zobrist::piece_square_key(
+        new_board[mv.to.0][mv.to.1],
+        zobrist::square_index(mv.to.0, mv.to.1),
+    );
+    if is_capture {
+        hash ^=
+            zobrist::piece_square_key(captured_piece, zobrist::square_index(mv.to.0, mv.to.1));
+    }
+    if is_en_passant_capture {
+        hash ^= zobrist::piece_square_key(
+            Piece::Pawn(opposite(color)),
+            zobrist::square_index(mv.from.0, mv.to.1),
+        );
+    }
+    if let Some(rook_from_file) = mv.castling_rook_from {
+        let rank = mv.from.0;
+        let rook_to_file = rook_destination_file(mv.to.1).ok_or(ErrorWrapper::InvalidMove)?;
+        hash ^= zobrist::piece_square_key(
+            Piece::Rook(color),
+            zobrist::square_index(rank, rook_from_file),
+        );
+        hash ^= zobrist::piece_square_key(
+            Piece::Rook(color),
+            zobrist::square_index(rank, rook_to_file),
+        );
+    }
+    if let Some((file, _)) = game.en_passant() {
+        hash ^= zobrist::en_passant_file_key(file);
+    }
+    if let Some((file, _)) = en_passant {
+        hash ^= zobrist::en_passant_file_key(file);
+    }
+    for (old, new, color) in [
+        (old_white_castling, white_castling, Color::White),
+        (old_black_castling, black_castling, Color::Black),
+    ] {
+        if old.kingside_rook_file() != new.kingside_rook_file() {
+            hash ^= zobrist::kingside_castling_key(color);
+        }
+        if old.queenside_rook_file() != new.queenside_rook_file() {
+            hash ^= zobrist::queenside_castling_key(color);
+        }
+    }
+    hash ^= zobrist::side_to_move_key();
+
+    let mut new_game = ChessGame::new(
+        new_board,
+        opposite(color),
+        white_castling,
+        black_castling,
+        en_passant,
+        halfmove,
+        fullmove,
+    );
+    new_game.set_hash(hash);
+    Ok(new_game)
+}
+
+/// Counts the leaf nodes of the legal move tree rooted at `game` to the
+/// given depth, the standard perft correctness check for move generators.
+pub fn perft(game: &ChessGame, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = legal_moves(game);
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    moves
+        .into_iter()
+        .map(|mv| {
+            let next = make_move(game, mv).expect("legal_moves only ever produces valid moves");
+            perft(&next, depth - 1)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fen_parser::create_from_fen;
+
+    #[test]
+    fn starting_position_has_twenty_moves() {
+        let game = create_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+            .unwrap();
+
+        assert_eq!(legal_moves(&game).len(), 20);
+    }
+
+    #[test]
+    fn pinned_knight_has_no_legal_moves() {
+        let game = create_from_fen("4k3/8/8/8/8/2b5/3N4/4K3 w - - 0 1").unwrap();
+
+        let moves = legal_moves(&game);
+        assert!(moves.iter().all(|mv| mv.from != (6, 3)));
+    }
+
+    #[test]
+    fn pawn_promotes_to_all_four_pieces() {
+        let game = create_from_fen("8/4P3/8/8/8/8/8/4k2K w - - 0 1").unwrap();
+
+        let promotions = legal_moves(&game)
+            .into_iter()
+            .filter(|mv| mv.from == (1, 4))
+            .count();
+
+        assert_eq!(promotions, 4);
+    }
+
+    #[test]
+    fn en_passant_capture_is_generated() {
+        let game =
+            create_from_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3")
+                .unwrap();
+
+        let has_en_passant = legal_moves(&game)
+            .iter()
+            .any(|mv| mv.from == (3, 4) && mv.to == (2, 3));
+
+        assert!(has_en_passant);
+    }
+
+    #[test]
+    fn both_castling_moves_are_generated_when_the_path_is_clear() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        let moves = legal_moves(&game);
+        assert!(moves
+            .iter()
+            .any(|mv| mv.from == (7, 4) && mv.to == (7, 6) && mv.castling_rook_from == Some(7)));
+        assert!(moves
+            .iter()
+            .any(|mv| mv.from == (7, 4) && mv.to == (7, 2) && mv.castling_rook_from == Some(0)));
+    }
+
+    #[test]
+    fn castling_through_an_attacked_square_is_illegal() {
+        // The black rook on f2 attacks f1, a square the king must cross to
+        // reach g1, without putting the king itself in check.
+        let game = create_from_fen("r3k2r/8/8/8/8/8/5r2/R3K2R w KQkq - 0 1").unwrap();
+
+        let moves = legal_moves(&game);
+        assert!(!moves.iter().any(|mv| mv.from == (7, 4) && mv.to == (7, 6)));
+        assert!(moves
+            .iter()
+            .any(|mv| mv.from == (7, 4) && mv.to == (7, 2) && mv.castling_rook_from == Some(0)));
+    }
+
+    #[test]
+    fn castling_is_blocked_by_a_piece_between_king_and_rook() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R2NK2R w KQkq - 0 1").unwrap();
+
+        let moves = legal_moves(&game);
+        assert!(!moves.iter().any(|mv| mv.castling_rook_from == Some(0)));
+        assert!(moves
+            .iter()
+            .any(|mv| mv.from == (7, 4) && mv.to == (7, 6) && mv.castling_rook_from == Some(7)));
+    }
+
+    #[test]
+    fn castling_move_relocates_the_rook() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let mv = Move {
+            from: (7, 4),
+            to: (7, 6),
+            promotion: None,
+            castling_rook_from: Some(7),
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.board()[7][6], Piece::King(Color::White));
+        assert_eq!(next.board()[7][5], Piece::Rook(Color::White));
+        assert_eq!(next.board()[7][7], Piece::Empty);
+        assert_eq!(next.board()[7][4], Piece::Empty);
+    }
+
+    #[test]
+    fn castling_revokes_both_of_that_sides_rights() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let mv = Move {
+            from: (7, 4),
+            to: (7, 6),
+            promotion: None,
+            castling_rook_from: Some(7),
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.castling(Color::White), Castling::None);
+        assert_eq!(
+            next.castling(Color::Black),
+            Castling::Both {
+                kingside_rook_file: 7,
+                queenside_rook_file: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn a_non_castling_king_move_also_revokes_both_rights() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let mv = Move {
+            from: (7, 4),
+            to: (7, 5),
+            promotion: None,
+            castling_rook_from: None,
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.castling(Color::White), Castling::None);
+    }
+
+    #[test]
+    fn a_rook_move_revokes_only_that_sides_right() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let mv = Move {
+            from: (7, 7),
+            to: (7, 5),
+            promotion: None,
+            castling_rook_from: None,
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.castling(Color::White), Castling::QueenSide(0));
+    }
+
+    #[test]
+    fn capturing_a_rook_revokes_its_sides_right() {
+        let game = create_from_fen("r3k2r/7R/8/8/8/8/8/R3K3 w Qkq - 0 1").unwrap();
+        let mv = Move {
+            from: (1, 7),
+            to: (0, 7),
+            promotion: None,
+            castling_rook_from: None,
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.castling(Color::Black), Castling::QueenSide(0));
+    }
+
+    fn recomputed_hash(game: &ChessGame) -> u64 {
+        crate::zobrist::compute_hash(
+            game.board(),
+            game.turn(),
+            game.castling(Color::White),
+            game.castling(Color::Black),
+            game.en_passant(),
+        )
+    }
+
+    #[test]
+    fn incremental_hash_matches_a_fresh_computation_after_a_quiet_move() {
+        let game = create_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+            .unwrap();
+        let mv = Move {
+            from: (6, 4),
+            to: (4, 4),
+            promotion: None,
+            castling_rook_from: None,
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.hash(), recomputed_hash(&next));
+        assert_ne!(next.hash(), game.hash());
+    }
+
+    #[test]
+    fn incremental_hash_matches_a_fresh_computation_after_an_en_passant_capture() {
+        let game =
+            create_from_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3")
+                .unwrap();
+        let mv = Move {
+            from: (3, 4),
+            to: (2, 3),
+            promotion: None,
+            castling_rook_from: None,
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.hash(), recomputed_hash(&next));
+    }
+
+    #[test]
+    fn incremental_hash_matches_a_fresh_computation_after_a_castling_move() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let mv = Move {
+            from: (7, 4),
+            to: (7, 6),
+            promotion: None,
+            castling_rook_from: Some(7),
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.hash(), recomputed_hash(&next));
+    }
+
+    #[test]
+    fn incremental_hash_matches_a_fresh_computation_after_a_promotion() {
+        let game = create_from_fen("8/4P3/8/8/8/8/8/4k2K w - - 0 1").unwrap();
+        let mv = Move {
+            from: (1, 4),
+            to: (0, 4),
+            promotion: Some(Piece::Queen(Color::White)),
+            castling_rook_from: None,
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.hash(), recomputed_hash(&next));
+    }
+
+    #[test]
+    fn incremental_hash_matches_a_fresh_computation_after_a_capture() {
+        let game = create_from_fen("4k3/8/8/8/8/8/4r3/4K2R w K - 0 1").unwrap();
+        let mv = Move {
+            from: (7, 7),
+            to: (6, 4),
+            promotion: None,
+            castling_rook_from: None,
+        };
+
+        let next = make_move(&game, mv).unwrap();
+
+        assert_eq!(next.hash(), recomputed_hash(&next));
+    }
+
+    #[test]
+    fn make_move_rejects_a_malformed_castling_move() {
+        let game = create_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let mv = Move {
+            from: (7, 4),
+            to: (7, 5),
+            promotion: None,
+            castling_rook_from: Some(7),
+        };
+
+        assert_eq!(make_move(&game, mv), Err(ErrorWrapper::InvalidMove));
+    }
+}
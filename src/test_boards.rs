@@ -0,0 +1,169 @@
+use crate::game::{Color, MailBoxBoard, Piece};
+
+pub const STARTING_BOARD: MailBoxBoard = [
+    [
+        Piece::Rook(Color::Black),
+        Piece::Knight(Color::Black),
+        Piece::Bishop(Color::Black),
+        Piece::Queen(Color::Black),
+        Piece::King(Color::Black),
+        Piece::Bishop(Color::Black),
+        Piece::Knight(Color::Black),
+        Piece::Rook(Color::Black),
+    ],
+    [Piece::Pawn(Color::Black); 8],
+    [Piece::Empty; 8],
+    [Piece::Empty; 8],
+    [Piece::Empty; 8],
+    [Piece::Empty; 8],
+    [Piece::Pawn(Color::White); 8],
+    [
+        Piece::Rook(Color::White),
+        Piece::Knight(Color::White),
+        Piece::Bishop(Color::White),
+        Piece::Queen(Color::White),
+        Piece::King(Color::White),
+        Piece::Bishop(Color::White),
+        Piece::Knight(Color::White),
+        Piece::Rook(Color::White),
+    ],
+];
+
+pub const BOARD2: MailBoxBoard = [
+    [Piece::Empty; 8],
+    [
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::King(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+    ],
+    [
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+    ],
+    [
+        Piece::Empty,
+        Piece::Pawn(Color::Black),
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+        Piece::Pawn(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::Black),
+    ],
+    [
+        Piece::Pawn(Color::Black),
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+        Piece::Pawn(Color::Black),
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+    ],
+    [
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+        Piece::King(Color::White),
+    ],
+    [Piece::Empty; 8],
+    [Piece::Empty; 8],
+];
+
+pub const BOARD3: MailBoxBoard = [
+    [
+        Piece::Rook(Color::Black),
+        Piece::Empty,
+        Piece::Bishop(Color::Black),
+        Piece::King(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Rook(Color::Black),
+    ],
+    [
+        Piece::Pawn(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::Black),
+        Piece::Bishop(Color::White),
+        Piece::Pawn(Color::Black),
+        Piece::Knight(Color::White),
+        Piece::Pawn(Color::Black),
+    ],
+    [
+        Piece::Knight(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Knight(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+    ],
+    [
+        Piece::Empty,
+        Piece::Pawn(Color::Black),
+        Piece::Empty,
+        Piece::Knight(Color::White),
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+    ],
+    [
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+    ],
+    [
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+    ],
+    [
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+        Piece::Pawn(Color::White),
+        Piece::Empty,
+        Piece::King(Color::White),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+    ],
+    [
+        Piece::Queen(Color::Black),
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Empty,
+        Piece::Bishop(Color::Black),
+        Piece::Empty,
+    ],
+];
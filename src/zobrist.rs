@@ -0,0 +1,231 @@
+//! Zobrist hashing: a cheap, incrementally-updatable position key built by
+//! XOR-ing together a random `u64` for every (piece, color, square) on the
+//! board, plus keys for the side to move, each side's castling rights, and
+//! the en-passant file. Two positions with the same hash are (almost
+//! certainly) the same position, which is enough to drive threefold
+//! repetition detection and, later, a transposition table.
+//!
+//! Every key is generated at compile time from a fixed seed via a
+//! splitmix64 generator, so hashes are reproducible across runs and builds.
+
+use crate::bitboard::{color_index, piece_color, piece_kind_index};
+use crate::game::{Castling, Color, MailBoxBoard, Piece};
+
+const PIECE_KINDS: usize = 6;
+const COLORS: usize = 2;
+const SQUARES: usize = 64;
+const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut value = state;
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^= value >> 31;
+    (value, state)
+}
+
+struct Keys {
+    piece_square: [[[u64; SQUARES]; COLORS]; PIECE_KINDS],
+    side_to_move: u64,
+    castling: [[u64; 2]; COLORS],
+    en_passant_file: [u64; 8],
+}
+
+const fn generate_keys() -> Keys {
+    let mut state = SEED;
+    let mut piece_square = [[[0u64; SQUARES]; COLORS]; PIECE_KINDS];
+
+    let mut kind = 0;
+    while kind < PIECE_KINDS {
+        let mut color = 0;
+        while color < COLORS {
+            let mut square = 0;
+            while square < SQUARES {
+                let (value, next_state) = splitmix64(state);
+                piece_square[kind][color][square] = value;
+                state = next_state;
+                square += 1;
+            }
+            color += 1;
+        }
+        kind += 1;
+    }
+
+    let (side_to_move, state) = splitmix64(state);
+
+    let mut castling = [[0u64; 2]; COLORS];
+    let mut state = state;
+    let mut color = 0;
+    while color < COLORS {
+        let mut side = 0;
+        while side < 2 {
+            let (value, next_state) = splitmix64(state);
+            castling[color][side] = value;
+            state = next_state;
+            side += 1;
+        }
+        color += 1;
+    }
+
+    let mut en_passant_file = [0u64; 8];
+    let mut file = 0;
+    while file < 8 {
+        let (value, next_state) = splitmix64(state);
+        en_passant_file[file] = value;
+        state = next_state;
+        file += 1;
+    }
+
+    Keys {
+        piece_square,
+        side_to_move,
+        castling,
+        en_passant_file,
+    }
+}
+
+const KEYS: Keys = generate_keys();
+
+const KINGSIDE: usize = 0;
+const QUEENSIDE: usize = 1;
+
+/// The bitboard-style square index (`row * 8 + col`) for a `(row, col)` pair.
+pub fn square_index(row: usize, col: usize) -> usize {
+    row * 8 + col
+}
+
+/// The key for `piece` sitting on `square`, or `0` for an empty square so it
+/// can be XORed into a running hash unconditionally.
+pub fn piece_square_key(piece: Piece, square: usize) -> u64 {
+    match (piece_kind_index(piece), piece_color(piece)) {
+        (Some(kind), Some(color)) => KEYS.piece_square[kind][color_index(color)][square],
+        _ => 0,
+    }
+}
+
+/// The key toggled whenever it is Black's turn to move.
+pub fn side_to_move_key() -> u64 {
+    KEYS.side_to_move
+}
+
+/// The key for `color`'s kingside castling right, present whenever
+/// [`Castling::kingside_rook_file`] is `Some`.
+pub fn kingside_castling_key(color: Color) -> u64 {
+    KEYS.castling[color_index(color)][KINGSIDE]
+}
+
+/// The key for `color`'s queenside castling right, present whenever
+/// [`Castling::queenside_rook_file`] is `Some`.
+pub fn queenside_castling_key(color: Color) -> u64 {
+    KEYS.castling[color_index(color)][QUEENSIDE]
+}
+
+/// The key for an en-passant target on the given file (0 = a-file).
+pub fn en_passant_file_key(file: usize) -> u64 {
+    KEYS.en_passant_file[file]
+}
+
+/// Computes a position's hash from scratch. [`ChessGame::new`] calls it for
+/// every position it builds, including the ones built by
+/// [`crate::movegen::make_move`], which then replaces the result with its
+/// incrementally-updated hash.
+///
+/// [`ChessGame::new`]: crate::game::ChessGame::new
+pub fn compute_hash(
+    board: &MailBoxBoard,
+    turn: Color,
+    white_castling: Castling,
+    black_castling: Castling,
+    en_passant: Option<(usize, usize)>,
+) -> u64 {
+    let mut hash = 0;
+
+    for (row_index, row) in board.iter().enumerate() {
+        for (col_index, piece) in row.iter().enumerate() {
+            hash ^= piece_square_key(*piece, square_index(row_index, col_index));
+        }
+    }
+
+    if turn == Color::Black {
+        hash ^= side_to_move_key();
+    }
+
+    for (color, castling) in [(Color::White, white_castling), (Color::Black, black_castling)] {
+        if castling.kingside_rook_file().is_some() {
+            hash ^= kingside_castling_key(color);
+        }
+        if castling.queenside_rook_file().is_some() {
+            hash ^= queenside_castling_key(color);
+        }
+    }
+
+    if let Some((file, _)) = en_passant {
+        hash ^= en_passant_file_key(file);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn piece_square_keys_are_deterministic_across_calls() {
+        assert_eq!(
+            piece_square_key(Piece::Pawn(Color::White), 8),
+            piece_square_key(Piece::Pawn(Color::White), 8)
+        );
+    }
+
+    #[test]
+    fn different_squares_get_different_keys() {
+        assert_ne!(
+            piece_square_key(Piece::Pawn(Color::White), 8),
+            piece_square_key(Piece::Pawn(Color::White), 9)
+        );
+    }
+
+    #[test]
+    fn different_pieces_get_different_keys() {
+        assert_ne!(
+            piece_square_key(Piece::Pawn(Color::White), 8),
+            piece_square_key(Piece::Pawn(Color::Black), 8)
+        );
+        assert_ne!(
+            piece_square_key(Piece::Pawn(Color::White), 8),
+            piece_square_key(Piece::Knight(Color::White), 8)
+        );
+    }
+
+    #[test]
+    fn empty_squares_contribute_nothing() {
+        assert_eq!(piece_square_key(Piece::Empty, 8), 0);
+    }
+
+    #[test]
+    fn castling_keys_differ_by_color_and_side() {
+        let keys = [
+            kingside_castling_key(Color::White),
+            queenside_castling_key(Color::White),
+            kingside_castling_key(Color::Black),
+            queenside_castling_key(Color::Black),
+        ];
+
+        for (i, a) in keys.iter().enumerate() {
+            for (j, b) in keys.iter().enumerate() {
+                assert!(i == j || a != b);
+            }
+        }
+    }
+
+    #[test]
+    fn en_passant_file_keys_are_distinct() {
+        for file in 0..8 {
+            for other in 0..8 {
+                assert!(file == other || en_passant_file_key(file) != en_passant_file_key(other));
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use nogi::fen_parser::create_from_fen;
+use nogi::movegen::perft;
+
+const STARTING_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[test]
+fn perft_depth_one_from_starting_position() {
+    let game = create_from_fen(STARTING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 1), 20);
+}
+
+#[test]
+fn perft_depth_two_from_starting_position() {
+    let game = create_from_fen(STARTING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 2), 400);
+}
+
+#[test]
+fn perft_depth_three_from_starting_position() {
+    let game = create_from_fen(STARTING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 3), 8902);
+}
+
+#[test]
+fn perft_depth_four_from_starting_position() {
+    let game = create_from_fen(STARTING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 4), 197_281);
+}
+
+#[test]
+fn perft_depth_five_from_starting_position() {
+    let game = create_from_fen(STARTING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 5), 4_865_609);
+}
+
+/// The standard "kings and rooks" castling test position: both sides keep
+/// full castling rights and an open back rank, so unlike the standard
+/// starting position (where castling and promotion lines are too deep to
+/// reach within a few plies), this position exercises castling -
+/// and the checks it must pass - from depth one.
+const CASTLING_POSITION: &str = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+#[test]
+fn perft_depth_one_from_castling_position() {
+    let game = create_from_fen(CASTLING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 1), 26);
+}
+
+#[test]
+fn perft_depth_two_from_castling_position() {
+    let game = create_from_fen(CASTLING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 2), 568);
+}
+
+#[test]
+fn perft_depth_three_from_castling_position() {
+    let game = create_from_fen(CASTLING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 3), 13_744);
+}
+
+#[test]
+fn perft_depth_four_from_castling_position() {
+    let game = create_from_fen(CASTLING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 4), 314_346);
+}
+
+#[test]
+fn perft_depth_five_from_castling_position() {
+    let game = create_from_fen(CASTLING_POSITION).unwrap();
+
+    assert_eq!(perft(&game, 5), 7_594_526);
+}